@@ -72,6 +72,23 @@ pub fn set_preferred_port(port: u16) -> Result<(), String> {
     cli_manager::save_meta(&meta)
 }
 
+#[tauri::command]
+pub fn get_stop_grace_period() -> u64 {
+    cli_manager::load_meta()
+        .stop_grace_period_ms
+        .unwrap_or(crate::services::server_manager::DEFAULT_STOP_GRACE_PERIOD_MS)
+}
+
+#[tauri::command]
+pub fn set_stop_grace_period(ms: u64) -> Result<(), String> {
+    if ms > 30_000 {
+        return Err("Grace period must be at most 30000 ms".to_string());
+    }
+    let mut meta = cli_manager::load_meta();
+    meta.stop_grace_period_ms = Some(ms);
+    cli_manager::save_meta(&meta)
+}
+
 #[tauri::command]
 pub fn get_preferred_theme() -> String {
     cli_manager::load_meta()
@@ -1,9 +1,24 @@
-use crate::models::project::{Project, ProjectType};
+use crate::models::project::{Project, ProjectOpResult, ProjectSort, ProjectType};
 use crate::services::project_store;
 
+/// List registered projects, favorites first, then ordered by `sort`.
+/// Archived projects are skipped unless `include_archived` is set;
+/// `tag` keeps only projects carrying it.
 #[tauri::command]
-pub fn list_projects() -> Vec<Project> {
-    project_store::load().projects
+pub fn list_projects(
+    tag: Option<String>,
+    include_archived: Option<bool>,
+    sort: Option<ProjectSort>,
+) -> Vec<Project> {
+    let include_archived = include_archived.unwrap_or(false);
+    let mut projects: Vec<Project> = project_store::load()
+        .projects
+        .into_iter()
+        .filter(|p| include_archived || !p.is_archived)
+        .filter(|p| tag.as_deref().is_none_or(|t| project_store::has_tag(p, t)))
+        .collect();
+    project_store::sort_projects(&mut projects, sort);
+    projects
 }
 
 #[tauri::command]
@@ -39,9 +54,83 @@ pub fn remove_project(id: String) -> Result<(), String> {
     project_store::save(&store)
 }
 
+/// Unregister several projects at once. Only the app's project list changes;
+/// nothing on disk is deleted. Unknown ids are reported without aborting the batch.
+#[tauri::command]
+pub fn remove_projects(ids: Vec<String>) -> Result<Vec<ProjectOpResult>, String> {
+    let mut store = project_store::load();
+    let results = ids
+        .into_iter()
+        .map(|id| {
+            if store.projects.iter().any(|p| p.id == id) {
+                project_store::remove_project(&mut store, &id);
+                ProjectOpResult {
+                    id,
+                    success: true,
+                    error: None,
+                }
+            } else {
+                ProjectOpResult {
+                    error: Some(format!("Project {id} not found")),
+                    id,
+                    success: false,
+                }
+            }
+        })
+        .collect();
+    project_store::save(&store)?;
+    Ok(results)
+}
+
 #[tauri::command]
 pub fn switch_project(id: String) -> Result<(), String> {
     let mut store = project_store::load();
     project_store::set_active(&mut store, &id)?;
+    project_store::record_open(&mut store, &id)?;
+    project_store::save(&store)
+}
+
+#[tauri::command]
+pub fn set_project_archived(id: String, archived: bool) -> Result<(), String> {
+    let mut store = project_store::load();
+    project_store::set_archived(&mut store, &id, archived)?;
+    project_store::save(&store)
+}
+
+#[tauri::command]
+pub fn set_project_favorite(id: String, favorite: bool) -> Result<(), String> {
+    let mut store = project_store::load();
+    project_store::set_favorite(&mut store, &id, favorite)?;
+    project_store::save(&store)
+}
+
+#[tauri::command]
+pub fn add_project_tag(id: String, tag: String) -> Result<(), String> {
+    let mut store = project_store::load();
+    project_store::add_tag(&mut store, &id, &tag)?;
     project_store::save(&store)
 }
+
+#[tauri::command]
+pub fn remove_project_tag(id: String, tag: String) -> Result<(), String> {
+    let mut store = project_store::load();
+    project_store::remove_tag(&mut store, &id, &tag)?;
+    project_store::save(&store)
+}
+
+#[tauri::command]
+pub fn set_project_metadata(
+    id: String,
+    key: String,
+    value: serde_json::Value,
+) -> Result<(), String> {
+    let mut store = project_store::load();
+    project_store::set_metadata(&mut store, &id, &key, value)?;
+    project_store::save(&store)
+}
+
+#[tauri::command]
+pub fn get_project_metadata(id: String, key: String) -> Result<Option<serde_json::Value>, String> {
+    let store = project_store::load();
+    project_store::get_metadata(&store, &id, &key)
+}
@@ -1,4 +1,5 @@
-use crate::services::{project_store, server_manager::ServerManager};
+use crate::models::server::{PortConflict, StopOutcome};
+use crate::services::{project_store, server_manager, server_manager::ServerManager};
 use tauri::State;
 
 #[tauri::command]
@@ -15,7 +16,7 @@ pub async fn start_server(
 }
 
 #[tauri::command]
-pub async fn stop_server(server: State<'_, ServerManager>) -> Result<(), String> {
+pub async fn stop_server(server: State<'_, ServerManager>) -> Result<StopOutcome, String> {
     server.stop().await
 }
 
@@ -41,3 +42,12 @@ pub async fn server_health_check(server: State<'_, ServerManager>) -> Result<boo
 pub async fn get_server_port(server: State<'_, ServerManager>) -> Result<u16, String> {
     Ok(server.get_port().await)
 }
+
+#[tauri::command]
+pub async fn resolve_port_conflict(
+    server: State<'_, ServerManager>,
+    port: u16,
+    prefer_kill: bool,
+) -> Result<PortConflict, String> {
+    server_manager::resolve_port_conflict(&server, port, prefer_kill).await
+}
@@ -3,7 +3,7 @@ mod models;
 mod services;
 mod utils;
 
-use services::server_manager::ServerManager;
+use services::server_manager::{ServerManager, EXIT_STOP_GRACE_PERIOD_MS};
 use std::sync::atomic::{AtomicBool, Ordering};
 use tauri::Manager;
 use tauri_plugin_notification::NotificationExt;
@@ -36,18 +36,28 @@ pub fn run() {
             commands::project::get_active_project,
             commands::project::add_project,
             commands::project::remove_project,
+            commands::project::remove_projects,
             commands::project::switch_project,
+            commands::project::set_project_archived,
+            commands::project::set_project_favorite,
+            commands::project::add_project_tag,
+            commands::project::remove_project_tag,
+            commands::project::set_project_metadata,
+            commands::project::get_project_metadata,
             // Server commands
             commands::server::start_server,
             commands::server::stop_server,
             commands::server::restart_server,
             commands::server::server_health_check,
             commands::server::get_server_port,
+            commands::server::resolve_port_conflict,
             // App commands
             commands::app::get_app_state,
             commands::app::get_onboarding_status,
             commands::app::get_preferred_port,
             commands::app::set_preferred_port,
+            commands::app::get_stop_grace_period,
+            commands::app::set_stop_grace_period,
             commands::app::get_preferred_theme,
             commands::app::set_preferred_theme,
             commands::app::get_notify_sync,
@@ -90,7 +100,8 @@ pub fn run() {
                 // Safety net: stop the CLI server on any exit path
                 let server = app.state::<ServerManager>().inner().clone();
                 tauri::async_runtime::block_on(async move {
-                    let _ = server.stop().await;
+                    let grace = std::time::Duration::from_millis(EXIT_STOP_GRACE_PERIOD_MS);
+                    let _ = server.stop_with_grace(grace).await;
                 });
             }
         });
@@ -148,7 +159,8 @@ fn setup_system_tray(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Err
                 let app_handle = app.clone();
                 tauri::async_runtime::spawn(async move {
                     let server = app_handle.state::<ServerManager>();
-                    let _ = server.stop().await;
+                    let grace = std::time::Duration::from_millis(EXIT_STOP_GRACE_PERIOD_MS);
+                    let _ = server.stop_with_grace(grace).await;
                     app_handle.exit(0);
                 });
             }
@@ -9,6 +9,8 @@ pub struct CliMeta {
     pub installed_at: Option<String>,
     pub last_update_check: Option<String>,
     pub preferred_port: Option<u16>,
+    /// Milliseconds a server process gets after SIGTERM before it is SIGKILLed.
+    pub stop_grace_period_ms: Option<u64>,
     pub preferred_theme: Option<String>,
     pub notify_sync: Option<bool>,
     pub notify_update: Option<bool>,
@@ -1,2 +1,3 @@
 pub mod app_state;
 pub mod project;
+pub mod server;
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -15,6 +16,45 @@ pub struct Project {
     pub path: String,
     pub project_type: ProjectType,
     pub added_at: String,
+    /// Dormant projects are hidden from `list_projects` unless requested.
+    #[serde(default)]
+    pub is_archived: bool,
+    /// Favorites are listed before other projects.
+    #[serde(default)]
+    pub is_favorite: bool,
+    /// RFC 3339 time the project was last switched to.
+    #[serde(default)]
+    pub last_opened_at: Option<String>,
+    /// How many times the project has been switched to.
+    #[serde(default)]
+    pub open_count: u32,
+    /// User-defined labels for grouping projects (e.g. by client or stack).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Free-form per-project settings keyed as `namespace.key`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, serde_json::Value>,
+}
+
+/// Ordering for `list_projects`. Favorites always come first.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectSort {
+    /// Most recently opened first
+    Recent,
+    /// Most often opened first
+    Frequent,
+    /// Alphabetical, case-insensitive
+    Name,
+}
+
+/// Per-project outcome of a bulk operation, so one failure doesn't hide the rest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectOpResult {
+    pub id: String,
+    pub success: bool,
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
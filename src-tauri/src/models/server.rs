@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PortAction {
+    /// Nothing was listening on the port.
+    None,
+    /// The holding process was terminated and the port is now free.
+    Killed,
+    /// The port is still taken; `suggested_port` is the next free one.
+    SuggestedPort,
+    /// The port is still taken and none of the next 10 ports is free either.
+    NoFreePort,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortConflict {
+    pub port: u16,
+    pub pid: Option<u32>,
+    pub process_name: Option<String>,
+    pub action_taken: PortAction,
+    pub suggested_port: Option<u16>,
+    /// How the holding process ended when it was killed.
+    pub stop_outcome: Option<StopOutcome>,
+}
+
+/// How a process ended after a two-phase (SIGTERM, then SIGKILL) stop.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StopOutcome {
+    /// There was no process to stop.
+    NotRunning,
+    /// Exited on its own within the grace period after SIGTERM.
+    Graceful,
+    /// Still alive after the grace period and was SIGKILLed.
+    ForceKilled,
+}
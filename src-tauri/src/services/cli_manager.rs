@@ -237,13 +237,59 @@ pub async fn exec(
 
 // ── Release checking & download ────────────────────────────────────
 
+/// Failure of one `get_with_retry` attempt.
+enum FetchError {
+    /// The request itself failed (connect, timeout, ...).
+    Http(reqwest::Error),
+    /// The server answered with 5xx/429; kept so the caller sees the final response.
+    Status(reqwest::Response),
+}
+
+impl std::fmt::Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::Http(e) => write!(f, "{e}"),
+            FetchError::Status(resp) => write!(f, "status {}", resp.status()),
+        }
+    }
+}
+
+/// GET `url`, retrying connection errors, timeouts, 429s and 5xx responses
+/// with backoff. Any response — including a 5xx/429 once retries run out — is
+/// returned as `Ok` so the caller's own status handling still applies.
+async fn get_with_retry(url: &str) -> Result<reqwest::Response, reqwest::Error> {
+    use crate::utils::retry::{is_transient_http_error, retry_async, RetryPolicy};
+
+    let client = &reqwest::Client::new();
+    let policy = RetryPolicy::new(|e: &FetchError| match e {
+        FetchError::Http(e) => is_transient_http_error(e),
+        FetchError::Status(_) => true,
+    });
+    let result = retry_async(&policy, || async move {
+        let resp = client
+            .get(url)
+            .header("User-Agent", "skillshare-app")
+            .send()
+            .await
+            .map_err(FetchError::Http)?;
+        let status = resp.status();
+        if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            Err(FetchError::Status(resp))
+        } else {
+            Ok(resp)
+        }
+    })
+    .await;
+
+    match result {
+        Ok(resp) | Err(FetchError::Status(resp)) => Ok(resp),
+        Err(FetchError::Http(e)) => Err(e),
+    }
+}
+
 /// Returns (version_tag, download_url) for the latest GitHub release.
 pub async fn check_latest_release() -> Result<(String, String), String> {
-    let client = reqwest::Client::new();
-    let resp = client
-        .get("https://api.github.com/repos/runkids/skillshare/releases/latest")
-        .header("User-Agent", "skillshare-app")
-        .send()
+    let resp = get_with_retry("https://api.github.com/repos/runkids/skillshare/releases/latest")
         .await
         .map_err(|e| format!("HTTP request failed: {e}"))?;
 
@@ -314,11 +360,7 @@ pub async fn download_cli(url: &str) -> Result<String, String> {
     std::fs::create_dir_all(&tmp_dir).map_err(|e| format!("Failed to create temp dir: {e}"))?;
 
     // Download tarball
-    let client = reqwest::Client::new();
-    let resp = client
-        .get(url)
-        .header("User-Agent", "skillshare-app")
-        .send()
+    let resp = get_with_retry(url)
         .await
         .map_err(|e| format!("Download failed: {e}"))?;
 
@@ -1,4 +1,5 @@
-use crate::models::project::{Project, ProjectStore, ProjectType};
+use crate::models::project::{Project, ProjectSort, ProjectStore, ProjectType};
+use std::cmp::Reverse;
 use std::path::PathBuf;
 
 fn store_path() -> PathBuf {
@@ -34,6 +35,12 @@ pub fn add_project(
         path,
         project_type,
         added_at: chrono::Utc::now().to_rfc3339(),
+        is_archived: false,
+        is_favorite: false,
+        last_opened_at: None,
+        open_count: 0,
+        tags: Vec::new(),
+        metadata: Default::default(),
     };
     store.projects.push(project.clone());
     if store.active_project_id.is_none() {
@@ -45,7 +52,12 @@ pub fn add_project(
 pub fn remove_project(store: &mut ProjectStore, id: &str) {
     store.projects.retain(|p| p.id != id);
     if store.active_project_id.as_deref() == Some(id) {
-        store.active_project_id = store.projects.first().map(|p| p.id.clone());
+        // Never fall back to an archived project: it is hidden from list_projects
+        store.active_project_id = store
+            .projects
+            .iter()
+            .find(|p| !p.is_archived)
+            .map(|p| p.id.clone());
     }
 }
 
@@ -60,11 +72,137 @@ pub fn active_project_mode(store: &ProjectStore) -> (Option<String>, bool) {
     (dir, is_project)
 }
 
+/// Make `id` the active project. Switching to an archived project unarchives
+/// it so the active project is always visible in `list_projects`.
 pub fn set_active(store: &mut ProjectStore, id: &str) -> Result<(), String> {
-    if store.projects.iter().any(|p| p.id == id) {
-        store.active_project_id = Some(id.to_string());
-        Ok(())
+    find_project_mut(store, id)?.is_archived = false;
+    store.active_project_id = Some(id.to_string());
+    Ok(())
+}
+
+// ── Usage tracking ─────────────────────────────────────────────────
+
+/// Record that a project was opened, for `recent`/`frequent` sorting.
+pub fn record_open(store: &mut ProjectStore, id: &str) -> Result<(), String> {
+    let project = find_project_mut(store, id)?;
+    project.last_opened_at = Some(chrono::Utc::now().to_rfc3339());
+    project.open_count = project.open_count.saturating_add(1);
+    Ok(())
+}
+
+/// Sort projects in place: favorites first, then by `sort` (insertion order when `None`).
+pub fn sort_projects(projects: &mut [Project], sort: Option<ProjectSort>) {
+    match sort {
+        // RFC 3339 timestamps from chrono::Utc sort lexicographically;
+        // never-opened projects (None) go last.
+        Some(ProjectSort::Recent) => projects.sort_by_key(|p| Reverse(p.last_opened_at.clone())),
+        Some(ProjectSort::Frequent) => projects.sort_by_key(|p| Reverse(p.open_count)),
+        Some(ProjectSort::Name) => projects.sort_by_key(|p| p.name.to_lowercase()),
+        None => {}
+    }
+    // Stable sort keeps the order above within each group
+    projects.sort_by_key(|p| !p.is_favorite);
+}
+
+// ── Archive / favorite ─────────────────────────────────────────────
+
+/// Archiving the active project is refused so the UI never points at a hidden
+/// project; `set_active` and `remove_project` keep the same invariant.
+pub fn set_archived(store: &mut ProjectStore, id: &str, archived: bool) -> Result<(), String> {
+    if archived && store.active_project_id.as_deref() == Some(id) {
+        return Err(
+            "Cannot archive the active project. Switch to another project first.".to_string(),
+        );
+    }
+    find_project_mut(store, id)?.is_archived = archived;
+    Ok(())
+}
+
+pub fn set_favorite(store: &mut ProjectStore, id: &str, favorite: bool) -> Result<(), String> {
+    find_project_mut(store, id)?.is_favorite = favorite;
+    Ok(())
+}
+
+// ── Project tags ───────────────────────────────────────────────────
+
+fn find_project_mut<'a>(store: &'a mut ProjectStore, id: &str) -> Result<&'a mut Project, String> {
+    store
+        .projects
+        .iter_mut()
+        .find(|p| p.id == id)
+        .ok_or_else(|| format!("Project {id} not found"))
+}
+
+/// Tags are trimmed and compared case-insensitively; adding an existing tag is a no-op.
+pub fn add_tag(store: &mut ProjectStore, id: &str, tag: &str) -> Result<(), String> {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        return Err("Tag cannot be empty".to_string());
+    }
+    let project = find_project_mut(store, id)?;
+    if !project.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+        project.tags.push(tag.to_string());
+    }
+    Ok(())
+}
+
+pub fn remove_tag(store: &mut ProjectStore, id: &str, tag: &str) -> Result<(), String> {
+    let tag = tag.trim();
+    let project = find_project_mut(store, id)?;
+    project.tags.retain(|t| !t.eq_ignore_ascii_case(tag));
+    Ok(())
+}
+
+pub fn has_tag(project: &Project, tag: &str) -> bool {
+    let tag = tag.trim();
+    project.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+}
+
+// ── Project metadata ───────────────────────────────────────────────
+
+/// Metadata keys must be namespaced as `namespace.key` so features don't
+/// collide (e.g. `terminal.shell`, `server.port`).
+fn validate_metadata_key(key: &str) -> Result<(), String> {
+    let valid_part = |s: &str| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    };
+    match key.split_once('.') {
+        Some((ns, name)) if valid_part(ns) && valid_part(name) => Ok(()),
+        _ => Err(format!(
+            "Invalid metadata key '{key}': expected 'namespace.key' using letters, digits, '-' or '_'"
+        )),
+    }
+}
+
+/// Set a metadata value on a project. A `null` value removes the key.
+pub fn set_metadata(
+    store: &mut ProjectStore,
+    id: &str,
+    key: &str,
+    value: serde_json::Value,
+) -> Result<(), String> {
+    validate_metadata_key(key)?;
+    let project = find_project_mut(store, id)?;
+    if value.is_null() {
+        project.metadata.remove(key);
     } else {
-        Err(format!("Project {id} not found"))
+        project.metadata.insert(key.to_string(), value);
     }
+    Ok(())
+}
+
+pub fn get_metadata(
+    store: &ProjectStore,
+    id: &str,
+    key: &str,
+) -> Result<Option<serde_json::Value>, String> {
+    validate_metadata_key(key)?;
+    let project = store
+        .projects
+        .iter()
+        .find(|p| p.id == id)
+        .ok_or_else(|| format!("Project {id} not found"))?;
+    Ok(project.metadata.get(key).cloned())
 }
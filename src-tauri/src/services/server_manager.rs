@@ -1,10 +1,26 @@
+use crate::models::server::{PortAction, PortConflict, StopOutcome};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::process::{Child, Command};
 use tokio::sync::Mutex;
 
 const DEFAULT_PORT: u16 = 19420;
 const HEALTH_POLL_INTERVAL_MS: u64 = 500;
 const HEALTH_POLL_MAX_RETRIES: u32 = 20;
+/// Default time a process gets to exit after SIGTERM before it is SIGKILLed.
+/// Overridable via `CliMeta::stop_grace_period_ms`.
+pub const DEFAULT_STOP_GRACE_PERIOD_MS: u64 = 3000;
+/// Grace period used when the app itself is exiting, so quitting stays snappy.
+pub const EXIT_STOP_GRACE_PERIOD_MS: u64 = 300;
+const STOP_POLL_INTERVAL_MS: u64 = 100;
+
+/// Grace period from settings, falling back to the default.
+fn stop_grace_period() -> Duration {
+    let ms = crate::services::cli_manager::load_meta()
+        .stop_grace_period_ms
+        .unwrap_or(DEFAULT_STOP_GRACE_PERIOD_MS);
+    Duration::from_millis(ms)
+}
 
 /// Kill orphaned `skillshare` CLI processes listening on the given port range.
 /// This handles the case where a previous app instance was killed without
@@ -15,15 +31,25 @@ const HEALTH_POLL_MAX_RETRIES: u32 = 20;
 /// Also skips our own PID as a safety net.
 async fn kill_orphaned_servers(base_port: u16, end_port: u16) {
     let own_pid = std::process::id();
+    let grace = stop_grace_period();
 
     for port in base_port..=end_port {
         if !is_port_in_use(port).await {
             continue;
         }
-        // lsof -ti filters by port; -c /^skillshare$/ uses regex for exact
-        // command name match (avoids matching skillshare-app).
+        // lsof ORs its selectors unless -a is given, so -a is required to get
+        // only processes that are LISTENing on this port AND whose command
+        // name is exactly `skillshare` (-c regex avoids matching skillshare-app).
         let output = tokio::process::Command::new("lsof")
-            .args(["-ti", &format!("tcp:{port}"), "-c", "/^skillshare$/"])
+            .args([
+                "-a",
+                "-nP",
+                &format!("-iTCP:{port}"),
+                "-sTCP:LISTEN",
+                "-c",
+                "/^skillshare$/",
+                "-t",
+            ])
             .output()
             .await;
 
@@ -35,12 +61,8 @@ async fn kill_orphaned_servers(base_port: u16, end_port: u16) {
                         continue;
                     }
                     log::info!("Killing orphaned skillshare process (pid={pid}) on port {port}");
-                    let _ = tokio::process::Command::new("kill")
-                        .args(["-TERM", &pid.to_string()])
-                        .output()
-                        .await;
-                    // Give it a moment to exit
-                    tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+                    let outcome = terminate_gracefully(pid, grace).await;
+                    log::info!("Orphaned process {pid} stopped: {outcome:?}");
                 }
             }
         }
@@ -65,6 +87,15 @@ impl ServerManager {
         *self.port.lock().await
     }
 
+    /// PID of the server child we spawned, if it is still running.
+    pub async fn managed_pid(&self) -> Option<u32> {
+        self.process
+            .lock()
+            .await
+            .as_ref()
+            .and_then(|child| child.id())
+    }
+
     /// Start the skillshare UI server. Stops any existing process first.
     /// Tries ports from 19420 to 19430 until one works.
     ///
@@ -122,6 +153,11 @@ impl ServerManager {
             cmd.current_dir(&resolved);
         }
 
+        // Run the server in its own process group so stop() can signal the
+        // whole tree, including anything the CLI spawns.
+        #[cfg(unix)]
+        cmd.process_group(0);
+
         // Prevent the child from inheriting stdin and suppress stdout/stderr
         cmd.stdin(std::process::Stdio::null());
         cmd.stdout(std::process::Stdio::null());
@@ -146,15 +182,31 @@ impl ServerManager {
         Ok(chosen_port)
     }
 
-    /// Kill the running server process if any.
-    pub async fn stop(&self) -> Result<(), String> {
+    /// Stop the running server process if any, using the configured grace period.
+    pub async fn stop(&self) -> Result<StopOutcome, String> {
+        self.stop_with_grace(stop_grace_period()).await
+    }
+
+    /// Stop the running server process if any.
+    ///
+    /// Sends SIGTERM to the server's process group and waits up to `grace`
+    /// before SIGKILLing whatever is left, so the CLI can release its port and
+    /// any helper processes it spawned die with it.
+    pub async fn stop_with_grace(&self, grace: Duration) -> Result<StopOutcome, String> {
         let mut proc = self.process.lock().await;
-        if let Some(ref mut child) = *proc {
-            child.kill().await.ok();
-            child.wait().await.ok();
+        let outcome = match proc.as_mut() {
+            Some(child) => match child.id() {
+                Some(pid) => stop_child_group(child, pid, grace).await,
+                // Already reaped: nothing was signalled
+                None => StopOutcome::NotRunning,
+            },
+            None => StopOutcome::NotRunning,
+        };
+        if outcome != StopOutcome::NotRunning {
+            log::info!("Server stopped: {outcome:?}");
         }
         *proc = None;
-        Ok(())
+        Ok(outcome)
     }
 
     /// Restart the server with updated parameters.
@@ -210,3 +262,276 @@ async fn is_port_in_use(port: u16) -> bool {
         .await
         .is_ok()
 }
+
+// ── Port conflict resolution ───────────────────────────────────────
+
+/// Identify the process holding `port` and either kill it (`prefer_kill`)
+/// or suggest the next free port. Returns the decision so the UI can prompt.
+///
+/// Never kills the app's own process; in that case a free port is suggested
+/// instead. If the holder is our managed server it is stopped through
+/// `ServerManager::stop` so its whole process group goes and no stale child
+/// handle is left behind.
+pub async fn resolve_port_conflict(
+    server: &ServerManager,
+    port: u16,
+    prefer_kill: bool,
+) -> Result<PortConflict, String> {
+    if !is_port_in_use(port).await {
+        return Ok(PortConflict {
+            port,
+            pid: None,
+            process_name: None,
+            action_taken: PortAction::None,
+            suggested_port: None,
+            stop_outcome: None,
+        });
+    }
+
+    let (pid, process_name) = match find_port_holder(port).await {
+        Some((pid, name)) => (Some(pid), name),
+        None => (None, None),
+    };
+
+    let mut stop_outcome = None;
+    if prefer_kill {
+        if let Some(pid) = pid.filter(|&p| p != std::process::id()) {
+            let outcome = if server.managed_pid().await == Some(pid) {
+                log::info!("Port {port} is held by our own server (pid={pid}), stopping it");
+                server.stop().await?
+            } else {
+                log::info!("Killing process (pid={pid}) holding port {port}");
+                terminate_gracefully(pid, stop_grace_period()).await
+            };
+            log::info!("Process {pid} stopped: {outcome:?}");
+            stop_outcome = Some(outcome);
+
+            // Give it a moment to release the socket
+            for _ in 0..10 {
+                tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+                if !is_port_in_use(port).await {
+                    return Ok(PortConflict {
+                        port,
+                        pid: Some(pid),
+                        process_name,
+                        action_taken: PortAction::Killed,
+                        suggested_port: None,
+                        stop_outcome,
+                    });
+                }
+            }
+            log::warn!("Port {port} still in use after killing pid {pid}");
+        }
+    }
+
+    let mut suggested_port = None;
+    for candidate in port.saturating_add(1)..=port.saturating_add(10) {
+        if !is_port_in_use(candidate).await {
+            suggested_port = Some(candidate);
+            break;
+        }
+    }
+
+    let action_taken = if suggested_port.is_some() {
+        PortAction::SuggestedPort
+    } else {
+        PortAction::NoFreePort
+    };
+
+    Ok(PortConflict {
+        port,
+        pid,
+        process_name,
+        action_taken,
+        suggested_port,
+        stop_outcome,
+    })
+}
+
+/// Find the (pid, process name) of the process listening on `port`.
+#[cfg(not(target_os = "windows"))]
+async fn find_port_holder(port: u16) -> Option<(u32, Option<String>)> {
+    // -F pc prints machine-readable fields: `p<pid>` then `c<command>`
+    let output = Command::new("lsof")
+        .args(["-nP", &format!("-iTCP:{port}"), "-sTCP:LISTEN", "-F", "pc"])
+        .output()
+        .await
+        .ok()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut pid = None;
+    let mut name = None;
+    for line in stdout.lines() {
+        if let Some(p) = line.strip_prefix('p') {
+            if pid.is_some() {
+                break;
+            }
+            pid = p.trim().parse::<u32>().ok();
+        } else if let Some(c) = line.strip_prefix('c') {
+            name = Some(c.trim().to_string());
+        }
+    }
+    pid.map(|p| (p, name))
+}
+
+/// Find the (pid, process name) of the process listening on `port`.
+#[cfg(target_os = "windows")]
+async fn find_port_holder(port: u16) -> Option<(u32, Option<String>)> {
+    let output = Command::new("netstat")
+        .args(["-ano", "-p", "TCP"])
+        .output()
+        .await
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let suffix = format!(":{port}");
+
+    // Columns: Proto  Local Address  Foreign Address  State  PID
+    let pid = stdout.lines().find_map(|line| {
+        let cols: Vec<&str> = line.split_whitespace().collect();
+        if cols.len() == 5 && cols[1].ends_with(&suffix) && cols[3] == "LISTENING" {
+            cols[4].parse::<u32>().ok()
+        } else {
+            None
+        }
+    })?;
+
+    let name = Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            &format!("(Get-Process -Id {pid}).ProcessName"),
+        ])
+        .output()
+        .await
+        .ok()
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|n| !n.is_empty());
+
+    Some((pid, name))
+}
+
+// ── Two-phase process termination ──────────────────────────────────
+
+/// Stop our own server child: SIGTERM its process group, then poll until the
+/// child and all of its descendants are gone. Only if something is still alive
+/// when the grace period ends is the group SIGKILLed.
+async fn stop_child_group(child: &mut Child, pid: u32, grace: Duration) -> StopOutcome {
+    send_stop_signal(pid, true, false).await;
+
+    let deadline = tokio::time::Instant::now() + grace;
+    if tokio::time::timeout_at(deadline, child.wait())
+        .await
+        .is_ok()
+    {
+        // The leader is gone; descendants may still be exiting or awaiting
+        // reaping by init, so keep polling the group until the deadline.
+        loop {
+            if !is_alive(pid, true).await {
+                return StopOutcome::Graceful;
+            }
+            let now = tokio::time::Instant::now();
+            if now >= deadline {
+                break;
+            }
+            let poll = Duration::from_millis(STOP_POLL_INTERVAL_MS);
+            tokio::time::sleep(poll.min(deadline - now)).await;
+        }
+    }
+
+    log::warn!(
+        "Server (pid={pid}) did not exit within {}ms, force killing",
+        grace.as_millis()
+    );
+    send_stop_signal(pid, true, true).await;
+    child.kill().await.ok();
+    child.wait().await.ok();
+    StopOutcome::ForceKilled
+}
+
+/// Two-phase stop for a process we did not spawn (orphans, port holders):
+/// SIGTERM, poll until it exits or the grace period ends, then SIGKILL.
+async fn terminate_gracefully(pid: u32, grace: Duration) -> StopOutcome {
+    send_stop_signal(pid, false, false).await;
+
+    let polls = (grace.as_millis() / u128::from(STOP_POLL_INTERVAL_MS)).max(1);
+    for _ in 0..polls {
+        if !is_alive(pid, false).await {
+            return StopOutcome::Graceful;
+        }
+        tokio::time::sleep(Duration::from_millis(STOP_POLL_INTERVAL_MS)).await;
+    }
+
+    log::warn!(
+        "Process {pid} did not exit within {}ms, force killing",
+        grace.as_millis()
+    );
+    send_stop_signal(pid, false, true).await;
+    StopOutcome::ForceKilled
+}
+
+/// Send SIGTERM (or SIGKILL when `force`) to `pid`, or to its whole process
+/// group when `group` is set.
+#[cfg(not(target_os = "windows"))]
+async fn send_stop_signal(pid: u32, group: bool, force: bool) {
+    let signal = if force { "-KILL" } else { "-TERM" };
+    let target = if group {
+        format!("-{pid}")
+    } else {
+        pid.to_string()
+    };
+    let _ = Command::new("kill")
+        .args([signal, "--", &target])
+        .output()
+        .await;
+}
+
+/// Ask `pid` and its child processes to exit; `force` terminates them.
+/// `taskkill /T` always targets the whole tree, so `group` has no effect.
+#[cfg(target_os = "windows")]
+async fn send_stop_signal(pid: u32, _group: bool, force: bool) {
+    let pid = pid.to_string();
+    let mut args = vec!["/PID", pid.as_str(), "/T"];
+    if force {
+        args.push("/F");
+    }
+    let _ = Command::new("taskkill").args(&args).output().await;
+}
+
+/// Whether `pid` (or any member of its process group when `group` is set) is
+/// still running. Uses `ps` rather than `kill -0`: the latter also succeeds for
+/// zombies (exited but not yet reaped by init), which would make a clean exit
+/// look like a hang and trigger a needless SIGKILL.
+#[cfg(not(target_os = "windows"))]
+async fn is_alive(pid: u32, group: bool) -> bool {
+    let pid_str = pid.to_string();
+    let output = if group {
+        Command::new("ps")
+            .args(["-A", "-o", "pgid=,stat="])
+            .output()
+            .await
+    } else {
+        Command::new("ps")
+            .args(["-o", "pid=,stat=", "-p", &pid_str])
+            .output()
+            .await
+    };
+    let Ok(output) = output else {
+        return false;
+    };
+
+    String::from_utf8_lossy(&output.stdout).lines().any(|line| {
+        let mut cols = line.split_whitespace();
+        cols.next() == Some(pid_str.as_str())
+            && cols.next().is_some_and(|stat| !stat.starts_with('Z'))
+    })
+}
+
+#[cfg(target_os = "windows")]
+async fn is_alive(pid: u32, _group: bool) -> bool {
+    Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {pid}"), "/NH"])
+        .output()
+        .await
+        .map(|o| String::from_utf8_lossy(&o.stdout).contains(&pid.to_string()))
+        .unwrap_or(false)
+}
@@ -0,0 +1,310 @@
+use std::future::Future;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Controls how `retry_async` retries a failing operation.
+///
+/// Delays grow exponentially from `base_delay` (1x, 2x, 4x, ...) and are
+/// capped at `max_delay`. With `jitter` on, each delay is randomized within
+/// its upper half so concurrent callers don't retry in lockstep.
+#[derive(Clone)]
+pub struct RetryPolicy<E> {
+    /// Total attempts including the first one. `1` means no retries.
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub jitter: bool,
+    /// Decides whether an error is worth retrying (e.g. timeouts, 5xx).
+    pub is_retryable: fn(&E) -> bool,
+    /// When set, retrying stops as soon as the channel holds `true`, including
+    /// mid-delay. Set via `RetryPolicy { cancel: Some(rx), ..RetryPolicy::new(f) }`.
+    pub cancel: Option<watch::Receiver<bool>>,
+}
+
+impl<E> RetryPolicy<E> {
+    pub fn new(is_retryable: fn(&E) -> bool) -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+            jitter: true,
+            is_retryable,
+            cancel: None,
+        }
+    }
+
+    /// Delay to wait before retry number `retry` (1-based).
+    fn delay_for(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+        if self.jitter {
+            let half = delay / 2;
+            half + half.mul_f64(jitter_fraction())
+        } else {
+            delay
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(|rx| *rx.borrow())
+    }
+}
+
+/// Run `op` until it succeeds, returns a non-retryable error, runs out of
+/// attempts, or the policy's cancel signal fires. The last error is returned
+/// on failure.
+pub async fn retry_async<T, E, F, Fut>(policy: &RetryPolicy<E>, mut op: F) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: std::fmt::Display,
+{
+    let mut attempt = 1;
+    loop {
+        let err = match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) => e,
+        };
+
+        if attempt >= policy.max_attempts || !(policy.is_retryable)(&err) || policy.is_cancelled() {
+            return Err(err);
+        }
+
+        let delay = policy.delay_for(attempt);
+        log::info!(
+            "Attempt {attempt}/{} failed: {err} — retrying in {}ms",
+            policy.max_attempts,
+            delay.as_millis()
+        );
+
+        match policy.cancel.clone() {
+            Some(mut cancel) => {
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {}
+                    Ok(_) = cancel.wait_for(|cancelled| *cancelled) => return Err(err),
+                }
+            }
+            None => tokio::time::sleep(delay).await,
+        }
+
+        attempt += 1;
+    }
+}
+
+/// Classifier for HTTP calls: retry connection failures, timeouts,
+/// rate limiting (429) and server errors (5xx).
+pub fn is_transient_http_error(err: &reqwest::Error) -> bool {
+    if err.is_timeout() || err.is_connect() {
+        return true;
+    }
+    err.status()
+        .is_some_and(|s| s.is_server_error() || s == reqwest::StatusCode::TOO_MANY_REQUESTS)
+}
+
+/// Pseudo-random value in [0, 1) for jitter; no need for a real RNG.
+///
+/// Each `RandomState` is seeded with fresh per-process random keys (bumped on
+/// every call), so successive values differ even within the same clock tick.
+fn jitter_fraction() -> f64 {
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    hasher.write_u128(nanos);
+    // Top 53 bits fill an f64 mantissa exactly
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use tokio::time::Instant;
+
+    fn policy(max_attempts: u32) -> RetryPolicy<String> {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            jitter: false,
+            ..RetryPolicy::new(|e| !e.starts_with("fatal"))
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn succeeds_on_first_try_without_waiting() {
+        let calls = Cell::new(0);
+        let start = Instant::now();
+        let result: Result<u32, String> = retry_async(&policy(3), || {
+            calls.set(calls.get() + 1);
+            async { Ok(7) }
+        })
+        .await;
+
+        assert_eq!(result, Ok(7));
+        assert_eq!(calls.get(), 1);
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn succeeds_after_retryable_failures() {
+        let calls = Cell::new(0);
+        let start = Instant::now();
+        let result = retry_async(&policy(5), || {
+            calls.set(calls.get() + 1);
+            let n = calls.get();
+            async move {
+                if n < 3 {
+                    Err(format!("flaky {n}"))
+                } else {
+                    Ok(n)
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result, Ok(3));
+        assert_eq!(calls.get(), 3);
+        // 100ms before retry 1, 200ms before retry 2
+        assert_eq!(start.elapsed(), Duration::from_millis(300));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stops_at_max_attempts_with_last_error() {
+        let calls = Cell::new(0);
+        let start = Instant::now();
+        let result: Result<(), String> = retry_async(&policy(3), || {
+            calls.set(calls.get() + 1);
+            let n = calls.get();
+            async move { Err(format!("flaky {n}")) }
+        })
+        .await;
+
+        assert_eq!(result, Err("flaky 3".to_string()));
+        assert_eq!(calls.get(), 3);
+        assert_eq!(start.elapsed(), Duration::from_millis(300));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stops_immediately_on_non_retryable_error() {
+        let calls = Cell::new(0);
+        let start = Instant::now();
+        let result: Result<(), String> = retry_async(&policy(5), || {
+            calls.set(calls.get() + 1);
+            async { Err("fatal: bad request".to_string()) }
+        })
+        .await;
+
+        assert_eq!(result, Err("fatal: bad request".to_string()));
+        assert_eq!(calls.get(), 1);
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn does_not_retry_once_cancelled() {
+        let (tx, rx) = watch::channel(false);
+        let policy = RetryPolicy {
+            cancel: Some(rx),
+            ..policy(5)
+        };
+        let _ = tx.send(true);
+
+        let calls = Cell::new(0);
+        let start = Instant::now();
+        let result: Result<(), String> = retry_async(&policy, || {
+            calls.set(calls.get() + 1);
+            async { Err("flaky".to_string()) }
+        })
+        .await;
+
+        assert_eq!(result, Err("flaky".to_string()));
+        assert_eq!(calls.get(), 1);
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn cancellation_interrupts_backoff_sleep() {
+        let (tx, rx) = watch::channel(false);
+        let policy = RetryPolicy {
+            base_delay: Duration::from_secs(10),
+            max_delay: Duration::from_secs(10),
+            cancel: Some(rx),
+            ..policy(5)
+        };
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            let _ = tx.send(true);
+        });
+
+        let calls = Cell::new(0);
+        let start = Instant::now();
+        let result: Result<(), String> = retry_async(&policy, || {
+            calls.set(calls.get() + 1);
+            async { Err("flaky".to_string()) }
+        })
+        .await;
+
+        assert_eq!(result, Err("flaky".to_string()));
+        assert_eq!(calls.get(), 1);
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn dropped_cancel_sender_does_not_cancel() {
+        let (tx, rx) = watch::channel(false);
+        drop(tx);
+        let policy = RetryPolicy {
+            cancel: Some(rx),
+            ..policy(3)
+        };
+
+        let calls = Cell::new(0);
+        let result: Result<(), String> = retry_async(&policy, || {
+            calls.set(calls.get() + 1);
+            async { Err("flaky".to_string()) }
+        })
+        .await;
+
+        assert_eq!(result, Err("flaky".to_string()));
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn delay_grows_exponentially_up_to_cap() {
+        let policy = policy(10);
+        let delays: Vec<u128> = (1..=6).map(|r| policy.delay_for(r).as_millis()).collect();
+        assert_eq!(delays, vec![100, 200, 400, 800, 1000, 1000]);
+        // Huge retry counts saturate instead of overflowing
+        assert_eq!(policy.delay_for(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn jitter_stays_within_upper_half_and_varies() {
+        let policy = RetryPolicy {
+            jitter: true,
+            ..policy(10)
+        };
+        for retry in 1..=6 {
+            let full = RetryPolicy {
+                jitter: false,
+                ..policy.clone()
+            }
+            .delay_for(retry);
+            let samples: Vec<Duration> = (0..200).map(|_| policy.delay_for(retry)).collect();
+            for d in &samples {
+                assert!(
+                    *d >= full / 2 && *d <= full,
+                    "{d:?} outside [{:?}, {full:?}]",
+                    full / 2
+                );
+            }
+            assert!(
+                samples.iter().any(|d| *d != samples[0]),
+                "jitter produced identical delays for retry {retry}"
+            );
+        }
+    }
+}
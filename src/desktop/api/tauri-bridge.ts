@@ -6,6 +6,20 @@ export interface Project {
   path: string;
   projectType: 'global' | 'project';
   addedAt: string;
+  isArchived: boolean;
+  isFavorite: boolean;
+  lastOpenedAt: string | null;
+  openCount: number;
+  tags?: string[];
+  metadata?: Record<string, unknown>;
+}
+
+export type ProjectSort = 'recent' | 'frequent' | 'name';
+
+export interface ProjectOpResult {
+  id: string;
+  success: boolean;
+  error: string | null;
 }
 
 export interface OnboardingStatus {
@@ -23,6 +37,17 @@ export interface AppInfo {
   onboarding: OnboardingStatus;
 }
 
+export type StopOutcome = 'not_running' | 'graceful' | 'force_killed';
+
+export interface PortConflict {
+  port: number;
+  pid: number | null;
+  processName: string | null;
+  actionTaken: 'none' | 'killed' | 'suggested_port' | 'no_free_port';
+  suggestedPort: number | null;
+  stopOutcome: StopOutcome | null;
+}
+
 export const tauriBridge = {
   // CLI commands
   detectCli: () => invoke<string | null>('detect_cli'),
@@ -34,27 +59,43 @@ export const tauriBridge = {
     invoke<string>('run_cli', { cliPath, args, workingDir }),
 
   // Project commands
-  listProjects: () => invoke<Project[]>('list_projects'),
+  listProjects: (tag?: string, includeArchived?: boolean, sort?: ProjectSort) =>
+    invoke<Project[]>('list_projects', { tag, includeArchived, sort }),
   getActiveProject: () => invoke<Project | null>('get_active_project'),
   addProject: (name: string, path: string, projectType: 'global' | 'project') =>
     invoke<Project>('add_project', { name, path, projectType }),
   removeProject: (id: string) => invoke<void>('remove_project', { id }),
+  removeProjects: (ids: string[]) => invoke<ProjectOpResult[]>('remove_projects', { ids }),
   switchProject: (id: string) => invoke<void>('switch_project', { id }),
+  setProjectArchived: (id: string, archived: boolean) =>
+    invoke<void>('set_project_archived', { id, archived }),
+  setProjectFavorite: (id: string, favorite: boolean) =>
+    invoke<void>('set_project_favorite', { id, favorite }),
+  addProjectTag: (id: string, tag: string) => invoke<void>('add_project_tag', { id, tag }),
+  removeProjectTag: (id: string, tag: string) => invoke<void>('remove_project_tag', { id, tag }),
+  setProjectMetadata: (id: string, key: string, value: unknown) =>
+    invoke<void>('set_project_metadata', { id, key, value }),
+  getProjectMetadata: <T = unknown>(id: string, key: string) =>
+    invoke<T | null>('get_project_metadata', { id, key }),
 
   // Server commands
   startServer: (cliPath: string, projectDir?: string) =>
     invoke<number>('start_server', { cliPath, projectDir }),
-  stopServer: () => invoke<void>('stop_server'),
+  stopServer: () => invoke<StopOutcome>('stop_server'),
   restartServer: (cliPath: string, projectDir?: string) =>
     invoke<number>('restart_server', { cliPath, projectDir }),
   healthCheck: () => invoke<boolean>('server_health_check'),
   getServerPort: () => invoke<number>('get_server_port'),
+  resolvePortConflict: (port: number, preferKill: boolean) =>
+    invoke<PortConflict>('resolve_port_conflict', { port, preferKill }),
 
   // App commands
   getAppState: () => invoke<AppInfo>('get_app_state'),
   getOnboardingStatus: () => invoke<OnboardingStatus>('get_onboarding_status'),
   getPreferredPort: () => invoke<number>('get_preferred_port'),
   setPreferredPort: (port: number) => invoke<void>('set_preferred_port', { port }),
+  getStopGracePeriod: () => invoke<number>('get_stop_grace_period'),
+  setStopGracePeriod: (ms: number) => invoke<void>('set_stop_grace_period', { ms }),
   getPreferredTheme: () => invoke<string>('get_preferred_theme'),
   setPreferredTheme: (theme: string) => invoke<void>('set_preferred_theme', { theme }),
   getNotifySync: () => invoke<boolean>('get_notify_sync'),